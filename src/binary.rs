@@ -0,0 +1,517 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::fs;
+
+/// A referenced .NET assembly, as found in the CLI metadata `AssemblyRef`
+/// table rather than declared in a project file.
+#[derive(Debug)]
+pub struct AssemblyReference {
+    pub name: String,
+    pub version: (u16, u16, u16, u16),
+}
+
+/// Everything discovered by reading a compiled binary directly: managed
+/// assembly references for .NET images, or imported module names for
+/// native PE images (never both, since a module is either one or the other).
+#[derive(Debug, Default)]
+pub struct BinaryDependencies {
+    pub assemblies: Vec<AssemblyReference>,
+    pub native_imports: Vec<String>,
+}
+
+/// The result of comparing what a project file declares against what the
+/// compiled binary actually uses.
+#[derive(Debug, Default)]
+pub struct Reconciliation {
+    pub declared_but_unused: Vec<String>,
+    pub used_but_undeclared: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum BinaryError {
+    Io(std::io::Error),
+    Pe(goblin::error::Error),
+    NotManaged,
+    Malformed(&'static str),
+}
+
+impl Display for BinaryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryError::Io(e) => write!(f, "could not read file: {e}"),
+            BinaryError::Pe(e) => write!(f, "could not parse PE: {e}"),
+            BinaryError::NotManaged => write!(f, "no CLR metadata found (not a .NET assembly)"),
+            BinaryError::Malformed(reason) => write!(f, "malformed CLI metadata: {reason}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for BinaryError {
+    fn from(e: std::io::Error) -> Self {
+        BinaryError::Io(e)
+    }
+}
+
+impl From<goblin::error::Error> for BinaryError {
+    fn from(e: goblin::error::Error) -> Self {
+        BinaryError::Pe(e)
+    }
+}
+
+/// Open `path` and discover its real dependencies: for a .NET assembly, the
+/// `AssemblyRef` table of its CLI metadata; for a native PE, its import
+/// directory's module names.
+pub fn inspect(path: &Path) -> Result<BinaryDependencies, BinaryError> {
+    let bytes = fs::read(path)?;
+    let pe = goblin::pe::PE::parse(&bytes)?;
+
+    if let Some(clr_dir) = pe.header.optional_header.as_ref().and_then(|oh| oh.data_directories.get_clr_runtime_header()) {
+        let assemblies = parse_assembly_refs(&bytes, &pe, clr_dir.virtual_address)?;
+        return Ok(BinaryDependencies { assemblies, native_imports: Vec::new() });
+    }
+
+    let native_imports = pe.imports.iter()
+        .map(|import| import.dll.to_string())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    Ok(BinaryDependencies { assemblies: Vec::new(), native_imports })
+}
+
+/// Extract the `Include` values of every `Reference`, `PackageReference` and
+/// `ProjectReference` declared in a project file, for reconciling against
+/// what the compiled binary actually uses.
+pub fn declared_references(project_path: &str) -> Vec<String> {
+    let xml = match fs::read_to_string(project_path) {
+        Ok(xml) => xml,
+        Err(e) => {
+            eprintln!("Unable to read \"{project_path}\" for reconciliation: {e}");
+            return Vec::new();
+        }
+    };
+
+    let tree = match crate::xml_tree::parse(&xml) {
+        Ok(tree) => tree,
+        Err(e) => {
+            eprintln!("Unable to parse \"{project_path}\" for reconciliation: {e}");
+            return Vec::new();
+        }
+    };
+
+    ["Reference", "PackageReference", "ProjectReference"].iter()
+        .flat_map(|element_name| tree.descendants_named(element_name))
+        .filter_map(|element| element.attribute("include"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Flag names the project declares but the binary never uses, and names the
+/// binary uses but the project never declares.
+pub fn reconcile(declared: &[String], discovered: &[String]) -> Reconciliation {
+    let declared_set: HashSet<&str> = declared.iter().map(String::as_str).collect();
+    let discovered_set: HashSet<&str> = discovered.iter().map(String::as_str).collect();
+
+    Reconciliation {
+        declared_but_unused: declared.iter()
+            .filter(|name| !discovered_set.contains(name.as_str()))
+            .cloned()
+            .collect(),
+        used_but_undeclared: discovered.iter()
+            .filter(|name| !declared_set.contains(name.as_str()))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Resolve a relative virtual address to a file offset using the section
+/// table, so the caller never has to guess at the file layout. Takes the
+/// section table directly, rather than the whole [`goblin::pe::PE`], so it
+/// can be exercised with hand-built section headers in tests.
+fn rva_to_offset(sections: &[goblin::pe::section_table::SectionTable], rva: u32) -> Option<usize> {
+    for section in sections {
+        let start = section.virtual_address;
+        let Some(end) = start.checked_add(section.virtual_size.max(section.size_of_raw_data)) else {
+            continue; // malformed section header: size overflows its own virtual address
+        };
+        if rva >= start && rva < end {
+            return (rva - start).checked_add(section.pointer_to_raw_data).map(|offset| offset as usize);
+        }
+    }
+    None
+}
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn at(data: &'a [u8], pos: usize) -> Self {
+        Cursor { data, pos }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], BinaryError> {
+        let bytes = self.data.get(self.pos..self.pos + n)
+            .ok_or(BinaryError::Malformed("unexpected end of metadata"))?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, BinaryError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, BinaryError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, BinaryError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, BinaryError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn skip(&mut self, n: usize) -> Result<(), BinaryError> {
+        self.take(n).map(|_| ())
+    }
+}
+
+/// Heap index widths, per the `#~` stream's `HeapSizes` byte: 4 bytes
+/// instead of 2 once a heap grows past 64K.
+#[derive(Clone, Copy)]
+struct HeapSizes {
+    wide_strings: bool,
+    wide_guid: bool,
+    wide_blob: bool,
+}
+
+impl HeapSizes {
+    fn from_flags(flags: u8) -> Self {
+        HeapSizes {
+            wide_strings: flags & 0x01 != 0,
+            wide_guid: flags & 0x02 != 0,
+            wide_blob: flags & 0x04 != 0,
+        }
+    }
+
+    fn strings(self) -> usize { if self.wide_strings { 4 } else { 2 } }
+    fn guid(self) -> usize { if self.wide_guid { 4 } else { 2 } }
+    fn blob(self) -> usize { if self.wide_blob { 4 } else { 2 } }
+}
+
+// Table ids, per ECMA-335 II.22.
+const MODULE: usize = 0x00;
+const TYPE_REF: usize = 0x01;
+const TYPE_DEF: usize = 0x02;
+const FIELD_PTR: usize = 0x03;
+const FIELD: usize = 0x04;
+const METHOD_PTR: usize = 0x05;
+const METHOD_DEF: usize = 0x06;
+const PARAM_PTR: usize = 0x07;
+const PARAM: usize = 0x08;
+const INTERFACE_IMPL: usize = 0x09;
+const MEMBER_REF: usize = 0x0A;
+const CONSTANT: usize = 0x0B;
+const CUSTOM_ATTRIBUTE: usize = 0x0C;
+const FIELD_MARSHAL: usize = 0x0D;
+const DECL_SECURITY: usize = 0x0E;
+const CLASS_LAYOUT: usize = 0x0F;
+const FIELD_LAYOUT: usize = 0x10;
+const STANDALONE_SIG: usize = 0x11;
+const EVENT_MAP: usize = 0x12;
+const EVENT_PTR: usize = 0x13;
+const EVENT: usize = 0x14;
+const PROPERTY_MAP: usize = 0x15;
+const PROPERTY_PTR: usize = 0x16;
+const PROPERTY: usize = 0x17;
+const METHOD_SEMANTICS: usize = 0x18;
+const METHOD_IMPL: usize = 0x19;
+const MODULE_REF: usize = 0x1A;
+const TYPE_SPEC: usize = 0x1B;
+const IMPL_MAP: usize = 0x1C;
+const FIELD_RVA: usize = 0x1D;
+const ENC_LOG: usize = 0x1E;
+const ENC_MAP: usize = 0x1F;
+const ASSEMBLY: usize = 0x20;
+const ASSEMBLY_PROCESSOR: usize = 0x21;
+const ASSEMBLY_OS: usize = 0x22;
+const ASSEMBLY_REF: usize = 0x23;
+
+const TYPE_DEF_OR_REF: &[usize] = &[TYPE_DEF, TYPE_REF, TYPE_SPEC];
+const HAS_CONSTANT: &[usize] = &[FIELD, PARAM, PROPERTY];
+const HAS_CUSTOM_ATTRIBUTE: &[usize] = &[
+    METHOD_DEF, FIELD, TYPE_REF, TYPE_DEF, PARAM, INTERFACE_IMPL, MEMBER_REF, MODULE,
+    DECL_SECURITY, PROPERTY, EVENT, STANDALONE_SIG, MODULE_REF, TYPE_SPEC, ASSEMBLY,
+    ASSEMBLY_REF, 0x26 /* File */, 0x27 /* ExportedType */, 0x28 /* ManifestResource */,
+    0x2A /* GenericParam */, 0x2C /* GenericParamConstraint */, 0x2B /* MethodSpec */,
+];
+const HAS_FIELD_MARSHAL: &[usize] = &[FIELD, PARAM];
+const HAS_DECL_SECURITY: &[usize] = &[TYPE_DEF, METHOD_DEF, ASSEMBLY];
+const MEMBER_REF_PARENT: &[usize] = &[TYPE_DEF, TYPE_REF, MODULE_REF, METHOD_DEF, TYPE_SPEC];
+const HAS_SEMANTICS: &[usize] = &[EVENT, PROPERTY];
+const METHOD_DEF_OR_REF: &[usize] = &[METHOD_DEF, MEMBER_REF];
+const MEMBER_FORWARDED: &[usize] = &[FIELD, METHOD_DEF];
+const RESOLUTION_SCOPE: &[usize] = &[MODULE, MODULE_REF, ASSEMBLY_REF, TYPE_REF];
+
+/// Width, in bytes, of a coded index over `tables`: 2 bytes unless the
+/// largest referenced table has enough rows to overflow the bits left over
+/// once the table-discriminating tag is accounted for.
+fn coded_index_size(tables: &[usize], row_counts: &[u32; 64]) -> usize {
+    let tag_bits = usize::BITS - (tables.len() - 1).leading_zeros();
+    let max_rows = tables.iter().map(|&t| row_counts[t]).max().unwrap_or(0);
+    if max_rows < (1u32 << (16 - tag_bits)) { 2 } else { 4 }
+}
+
+fn row_size(table: usize, counts: &[u32; 64], heap: HeapSizes) -> usize {
+    let simple = |t: usize| if counts[t] > 0xFFFF { 4 } else { 2 };
+    let (s, g, b) = (heap.strings(), heap.guid(), heap.blob());
+
+    match table {
+        MODULE => 2 + s + g * 3,
+        TYPE_REF => coded_index_size(RESOLUTION_SCOPE, counts) + s * 2,
+        TYPE_DEF => 4 + s * 2 + coded_index_size(TYPE_DEF_OR_REF, counts) + simple(FIELD) + simple(METHOD_DEF),
+        FIELD_PTR => simple(FIELD),
+        FIELD => 2 + s + b,
+        METHOD_PTR => simple(METHOD_DEF),
+        METHOD_DEF => 4 + 2 + 2 + s + b + simple(PARAM),
+        PARAM_PTR => simple(PARAM),
+        PARAM => 2 + 2 + s,
+        INTERFACE_IMPL => simple(TYPE_DEF) + coded_index_size(TYPE_DEF_OR_REF, counts),
+        MEMBER_REF => coded_index_size(MEMBER_REF_PARENT, counts) + s + b,
+        CONSTANT => 1 + 1 + coded_index_size(HAS_CONSTANT, counts) + b,
+        CUSTOM_ATTRIBUTE => coded_index_size(HAS_CUSTOM_ATTRIBUTE, counts) + coded_index_size(METHOD_DEF_OR_REF, counts) + b,
+        FIELD_MARSHAL => coded_index_size(HAS_FIELD_MARSHAL, counts) + b,
+        DECL_SECURITY => 2 + coded_index_size(HAS_DECL_SECURITY, counts) + b,
+        CLASS_LAYOUT => 2 + 4 + simple(TYPE_DEF),
+        FIELD_LAYOUT => 4 + simple(FIELD),
+        STANDALONE_SIG => b,
+        EVENT_MAP => simple(TYPE_DEF) + simple(EVENT),
+        EVENT_PTR => simple(EVENT),
+        EVENT => 2 + s + coded_index_size(TYPE_DEF_OR_REF, counts),
+        PROPERTY_MAP => simple(TYPE_DEF) + simple(PROPERTY),
+        PROPERTY_PTR => simple(PROPERTY),
+        PROPERTY => 2 + s + b,
+        METHOD_SEMANTICS => 2 + simple(METHOD_DEF) + coded_index_size(HAS_SEMANTICS, counts),
+        METHOD_IMPL => simple(TYPE_DEF) + coded_index_size(METHOD_DEF_OR_REF, counts) * 2,
+        MODULE_REF => s,
+        TYPE_SPEC => b,
+        IMPL_MAP => 2 + coded_index_size(MEMBER_FORWARDED, counts) + s + simple(MODULE_REF),
+        FIELD_RVA => 4 + simple(FIELD),
+        ENC_LOG => 4 + 4,
+        ENC_MAP => 4,
+        ASSEMBLY => 4 + 2 * 4 + 4 + b + s * 2,
+        ASSEMBLY_PROCESSOR => 4,
+        ASSEMBLY_OS => 4 + 4 + 4,
+        ASSEMBLY_REF => 2 * 4 + 4 + b + s * 2 + b,
+        _ => 0,
+    }
+}
+
+fn read_c_str(heap: &[u8], offset: usize) -> Result<String, BinaryError> {
+    let rest = heap.get(offset..).ok_or(BinaryError::Malformed("string heap offset out of range"))?;
+    let end = offset + rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+    Ok(String::from_utf8_lossy(&heap[offset..end]).into_owned())
+}
+
+/// Walk the CLI metadata of a managed PE to list its `AssemblyRef` table,
+/// i.e. the assemblies it actually references at the IL level.
+fn parse_assembly_refs(bytes: &[u8], pe: &goblin::pe::PE, cor20_rva: u32) -> Result<Vec<AssemblyReference>, BinaryError> {
+    if cor20_rva == 0 {
+        return Err(BinaryError::NotManaged);
+    }
+    let cor20_offset = rva_to_offset(&pe.sections, cor20_rva).ok_or(BinaryError::Malformed("COR20 header out of range"))?;
+
+    let mut cor20 = Cursor::at(bytes, cor20_offset);
+    cor20.skip(8)?; // cb, MajorRuntimeVersion, MinorRuntimeVersion
+    let metadata_rva = cor20.read_u32()?;
+
+    let metadata_offset = rva_to_offset(&pe.sections, metadata_rva).ok_or(BinaryError::Malformed("metadata root out of range"))?;
+    let mut root = Cursor::at(bytes, metadata_offset);
+
+    if root.read_u32()? != 0x424A5342 {
+        return Err(BinaryError::Malformed("missing BSJB metadata signature"));
+    }
+    root.skip(8)?; // MajorVersion, MinorVersion, Reserved
+    let version_len = root.read_u32()? as usize;
+    root.skip(version_len)?;
+    root.skip(2)?; // Flags
+    let stream_count = root.read_u16()?;
+
+    let mut tables_stream = None;
+    let mut strings_stream = None;
+    for _ in 0..stream_count {
+        let offset = root.read_u32()? as usize;
+        let size = root.read_u32()? as usize;
+        let name_start = root.pos;
+        let name = read_c_str(bytes, name_start)?;
+        root.skip((name.len() + 1 + 3) & !3)?; // name is padded to a 4-byte boundary
+
+        match name.as_str() {
+            "#~" | "#-" => tables_stream = Some((metadata_offset + offset, size)),
+            "#Strings" => strings_stream = Some(metadata_offset + offset),
+            _ => (),
+        }
+    }
+
+    let (tables_offset, _) = tables_stream.ok_or(BinaryError::Malformed("no #~ metadata table stream"))?;
+    let strings_offset = strings_stream.ok_or(BinaryError::Malformed("no #Strings heap"))?;
+
+    parse_tables_stream(bytes, tables_offset, strings_offset)
+}
+
+/// Parse the `#~` tables stream itself (row counts, then every table up to
+/// and including `AssemblyRef`), once its offset and the `#Strings` heap's
+/// offset are known. Split out from [`parse_assembly_refs`] so it can be
+/// tested against a synthetic stream without needing a real PE image.
+fn parse_tables_stream(bytes: &[u8], tables_offset: usize, strings_offset: usize) -> Result<Vec<AssemblyReference>, BinaryError> {
+    let mut tables = Cursor::at(bytes, tables_offset);
+    tables.skip(4)?; // Reserved
+    tables.skip(2)?; // MajorVersion, MinorVersion
+    let heap_sizes = HeapSizes::from_flags(tables.read_u8()?);
+    tables.skip(1)?; // Reserved
+    let valid = tables.read_u64()?;
+    tables.skip(8)?; // Sorted
+
+    let mut row_counts = [0u32; 64];
+    for (table_id, count) in row_counts.iter_mut().enumerate() {
+        if valid & (1 << table_id) != 0 {
+            *count = tables.read_u32()?;
+        }
+    }
+
+    for table_id in 0..ASSEMBLY_REF {
+        if valid & (1 << table_id) != 0 {
+            tables.skip(row_size(table_id, &row_counts, heap_sizes) * row_counts[table_id] as usize)?;
+        }
+    }
+
+    let mut assemblies = Vec::with_capacity(row_counts[ASSEMBLY_REF] as usize);
+    for _ in 0..row_counts[ASSEMBLY_REF] {
+        let major = tables.read_u16()?;
+        let minor = tables.read_u16()?;
+        let build = tables.read_u16()?;
+        let revision = tables.read_u16()?;
+        tables.skip(4)?; // Flags
+        tables.skip(heap_sizes.blob())?; // PublicKeyOrToken
+
+        let name_index = if heap_sizes.strings() == 4 { tables.read_u32()? as usize } else { tables.read_u16()? as usize };
+        tables.skip(heap_sizes.strings())?; // Culture
+        tables.skip(heap_sizes.blob())?; // HashValue
+
+        assemblies.push(AssemblyReference {
+            name: read_c_str(bytes, strings_offset + name_index)?,
+            version: (major, minor, build, revision),
+        });
+    }
+
+    Ok(assemblies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use goblin::pe::section_table::SectionTable;
+
+    fn section(virtual_address: u32, virtual_size: u32, pointer_to_raw_data: u32) -> SectionTable {
+        SectionTable { virtual_address, virtual_size, size_of_raw_data: virtual_size, pointer_to_raw_data, ..Default::default() }
+    }
+
+    #[test]
+    fn rva_to_offset_finds_the_containing_section() {
+        let sections = vec![section(0x1000, 0x200, 0x400), section(0x2000, 0x200, 0x600)];
+        assert_eq!(rva_to_offset(&sections, 0x2010), Some(0x610));
+    }
+
+    #[test]
+    fn rva_to_offset_is_none_outside_every_section() {
+        let sections = vec![section(0x1000, 0x200, 0x400)];
+        assert_eq!(rva_to_offset(&sections, 0x5000), None);
+    }
+
+    #[test]
+    fn rva_to_offset_skips_a_section_whose_size_overflows_instead_of_panicking() {
+        let sections = vec![section(u32::MAX - 1, 0x200, 0x400), section(0x1000, 0x200, 0x400)];
+        assert_eq!(rva_to_offset(&sections, 0x1010), Some(0x410));
+    }
+
+    #[test]
+    fn heap_sizes_from_flags_reads_each_bit_independently() {
+        let narrow = HeapSizes::from_flags(0x00);
+        assert_eq!((narrow.strings(), narrow.guid(), narrow.blob()), (2, 2, 2));
+
+        let wide = HeapSizes::from_flags(0x01 | 0x02 | 0x04);
+        assert_eq!((wide.strings(), wide.guid(), wide.blob()), (4, 4, 4));
+    }
+
+    #[test]
+    fn coded_index_size_is_2_bytes_until_a_table_would_overflow_the_tag_bits() {
+        let mut counts = [0u32; 64];
+        assert_eq!(coded_index_size(RESOLUTION_SCOPE, &counts), 2);
+
+        counts[MODULE_REF] = 1 << 15; // RESOLUTION_SCOPE has 4 members: 2 tag bits, 14 bits left
+        assert_eq!(coded_index_size(RESOLUTION_SCOPE, &counts), 4);
+    }
+
+    #[test]
+    fn row_size_matches_the_known_assembly_ref_layout_for_narrow_heaps() {
+        let counts = [0u32; 64];
+        let heap = HeapSizes::from_flags(0x00);
+        assert_eq!(row_size(ASSEMBLY_REF, &counts, heap), 20);
+    }
+
+    #[test]
+    fn read_c_str_stops_at_the_first_nul() {
+        let heap = b"Foo.Bar\0Ignored";
+        assert_eq!(read_c_str(heap, 0).unwrap(), "Foo.Bar");
+    }
+
+    #[test]
+    fn read_c_str_rejects_an_out_of_range_offset() {
+        let heap = b"Foo\0";
+        assert!(read_c_str(heap, 100).is_err());
+    }
+
+    /// Build a synthetic `#~` tables stream declaring just one `AssemblyRef`
+    /// row, with narrow (2-byte) heap indexes, and confirm it parses without
+    /// needing a real PE image.
+    #[test]
+    fn parse_tables_stream_reads_a_single_assembly_ref_row() {
+        let mut tables = Vec::new();
+        tables.extend_from_slice(&[0, 0, 0, 0]); // Reserved
+        tables.extend_from_slice(&[0, 0]); // MajorVersion, MinorVersion
+        tables.push(0x00); // HeapSizes: all narrow
+        tables.push(0); // Reserved
+        tables.extend_from_slice(&(1u64 << ASSEMBLY_REF).to_le_bytes()); // Valid: AssemblyRef only
+        tables.extend_from_slice(&0u64.to_le_bytes()); // Sorted
+        tables.extend_from_slice(&1u32.to_le_bytes()); // AssemblyRef row count
+
+        tables.extend_from_slice(&1u16.to_le_bytes()); // MajorVersion
+        tables.extend_from_slice(&2u16.to_le_bytes()); // MinorVersion
+        tables.extend_from_slice(&3u16.to_le_bytes()); // BuildNumber
+        tables.extend_from_slice(&4u16.to_le_bytes()); // RevisionNumber
+        tables.extend_from_slice(&0u32.to_le_bytes()); // Flags
+        tables.extend_from_slice(&0u16.to_le_bytes()); // PublicKeyOrToken blob index
+        tables.extend_from_slice(&0u16.to_le_bytes()); // Name string index (first string in the heap below)
+        tables.extend_from_slice(&0u16.to_le_bytes()); // Culture string index
+        tables.extend_from_slice(&0u16.to_le_bytes()); // HashValue blob index
+
+        let strings_offset = tables.len();
+        let mut bytes = tables;
+        bytes.extend_from_slice(b"Foo.Bar\0");
+
+        let assemblies = parse_tables_stream(&bytes, 0, strings_offset).unwrap();
+        assert_eq!(assemblies.len(), 1);
+        assert_eq!(assemblies[0].name, "Foo.Bar");
+        assert_eq!(assemblies[0].version, (1, 2, 3, 4));
+    }
+
+    #[test]
+    fn parse_tables_stream_reports_a_truncated_stream_instead_of_panicking() {
+        let bytes = [0u8; 4]; // Reserved only, missing everything else
+        assert!(parse_tables_stream(&bytes, 0, 0).is_err());
+    }
+}
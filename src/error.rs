@@ -0,0 +1,39 @@
+use std::fmt::{Display, Formatter};
+
+/// A recoverable failure reading or parsing one project/solution file. Kept
+/// deliberately small: callers report it and move on to the next file
+/// rather than aborting the whole scan.
+#[derive(Debug)]
+pub(crate) enum Error {
+    Io(std::io::Error),
+    Xml(quick_xml::Error),
+    Utf8(std::str::Utf8Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Xml(e) => write!(f, "{e}"),
+            Error::Utf8(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<quick_xml::Error> for Error {
+    fn from(e: quick_xml::Error) -> Self {
+        Error::Xml(e)
+    }
+}
+
+impl From<std::str::Utf8Error> for Error {
+    fn from(e: std::str::Utf8Error) -> Self {
+        Error::Utf8(e)
+    }
+}
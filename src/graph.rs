@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::fs;
+
+use crate::{normalize_dll_name, xml_tree};
+
+/// A chain of `ProjectReference` edges, ordered from the dependent project
+/// down to the project that directly references the queried DLL, e.g.
+/// `["A.csproj", "B.csproj", "C.csproj"]` where `C.csproj` is the direct
+/// referencer.
+pub type Chain = Vec<String>;
+
+/// A directed graph of project-to-project references for a whole solution,
+/// built from each project's `ProjectReference` includes. Used to answer
+/// "which projects transitively depend on this DLL", not just "which
+/// projects reference it directly".
+pub struct ProjectGraph {
+    /// project path -> paths of projects it references via `ProjectReference`
+    edges: HashMap<String, Vec<String>>,
+    /// projects with a direct Reference/PackageReference to the queried DLL
+    direct_references: HashSet<String>,
+}
+
+impl ProjectGraph {
+    /// Build the graph for every project in `project_paths`, resolving each
+    /// `ProjectReference` include to an absolute path relative to the
+    /// referencing project's directory, and merging in `declared_dependencies`
+    /// (the solution's own `ProjectSection(ProjectDependencies)` edges,
+    /// see [`crate::solution::SolutionFile::dependency_paths`]) so a project
+    /// that declares a dependency there but not as a `ProjectReference` is
+    /// still part of the graph. A project that can't be read or parsed is
+    /// reported and otherwise skipped, rather than aborting the whole scan.
+    pub fn build(project_paths: &[String], dll_name: &str, declared_dependencies: &HashMap<String, Vec<String>>) -> ProjectGraph {
+        let dll_name = normalize_dll_name(dll_name);
+        let mut edges = HashMap::new();
+        let mut direct_references = HashSet::new();
+
+        for project_path in project_paths {
+            let tree = match read_project(project_path) {
+                Ok(tree) => tree,
+                Err(e) => {
+                    eprintln!("Skipping \"{project_path}\" while building the dependency graph: {e}");
+                    continue;
+                }
+            };
+
+            let mut references = project_references(project_path, &tree);
+            if let Some(declared) = declared_dependencies.get(project_path) {
+                for dependency in declared {
+                    if !references.contains(dependency) {
+                        references.push(dependency.clone());
+                    }
+                }
+            }
+            edges.insert(project_path.clone(), references);
+
+            let is_direct = tree.has_descendant_with_attribute("Reference", "include", &dll_name)
+                || tree.has_descendant_with_attribute("PackageReference", "include", &dll_name);
+            if is_direct {
+                direct_references.insert(project_path.clone());
+            }
+        }
+
+        ProjectGraph { edges, direct_references }
+    }
+
+    /// Every project that depends on the queried DLL transitively, mapped to
+    /// the chain(s) of `ProjectReference` edges leading to a project with a
+    /// direct reference. Projects with only a direct reference are not
+    /// included here.
+    pub fn transitive_dependents(&self) -> HashMap<String, Vec<Chain>> {
+        let reverse_edges = self.reverse_edges();
+        let mut result: HashMap<String, Vec<Chain>> = HashMap::new();
+
+        for direct in &self.direct_references {
+            let mut visited = HashSet::new();
+            visited.insert(direct.clone());
+
+            let mut queue: VecDeque<Chain> = VecDeque::new();
+            queue.push_back(vec![direct.clone()]);
+
+            while let Some(path_to_direct) = queue.pop_front() {
+                let head = path_to_direct.last().unwrap().clone();
+                if let Some(referencers) = reverse_edges.get(&head) {
+                    for referencer in referencers {
+                        if visited.insert(referencer.clone()) {
+                            let mut next = path_to_direct.clone();
+                            next.push(referencer.clone());
+
+                            let mut chain = next.clone();
+                            chain.reverse();
+                            result.entry(referencer.clone()).or_default().push(chain);
+
+                            queue.push_back(next);
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Detect cycles in the `ProjectReference` graph so callers can report
+    /// them instead of relying on the BFS in [`ProjectGraph::transitive_dependents`]
+    /// to terminate by luck.
+    pub fn detect_cycles(&self) -> Vec<Chain> {
+        let mut cycles = Vec::new();
+        let mut visited = HashSet::new();
+        let mut on_stack = HashSet::new();
+        let mut stack = Vec::new();
+
+        for node in self.edges.keys() {
+            if !visited.contains(node) {
+                self.visit_for_cycles(node, &mut visited, &mut on_stack, &mut stack, &mut cycles);
+            }
+        }
+
+        cycles
+    }
+
+    fn visit_for_cycles(
+        &self,
+        node: &str,
+        visited: &mut HashSet<String>,
+        on_stack: &mut HashSet<String>,
+        stack: &mut Vec<String>,
+        cycles: &mut Vec<Chain>,
+    ) {
+        visited.insert(node.to_string());
+        on_stack.insert(node.to_string());
+        stack.push(node.to_string());
+
+        if let Some(neighbors) = self.edges.get(node) {
+            for neighbor in neighbors {
+                if on_stack.contains(neighbor) {
+                    let start = stack.iter().position(|n| n == neighbor).unwrap();
+                    let mut cycle: Chain = stack[start..].to_vec();
+                    cycle.push(neighbor.clone());
+                    cycles.push(cycle);
+                } else if !visited.contains(neighbor) {
+                    self.visit_for_cycles(neighbor, visited, on_stack, stack, cycles);
+                }
+            }
+        }
+
+        stack.pop();
+        on_stack.remove(node);
+    }
+
+    fn reverse_edges(&self) -> HashMap<String, Vec<String>> {
+        let mut reverse: HashMap<String, Vec<String>> = HashMap::new();
+        for (project, references) in &self.edges {
+            for referenced in references {
+                reverse.entry(referenced.clone()).or_default().push(project.clone());
+            }
+        }
+        reverse
+    }
+}
+
+fn read_project(project_path: &str) -> Result<xml_tree::Element, crate::error::Error> {
+    let xml = fs::read_to_string(project_path)?;
+    xml_tree::parse(&xml)
+}
+
+/// Resolve every `<ProjectReference Include="...">` in `tree` to an absolute
+/// path relative to `project_path`'s directory.
+fn project_references(project_path: &str, tree: &xml_tree::Element) -> Vec<String> {
+    let project_dir = Path::new(project_path).parent().unwrap_or_else(|| Path::new("."));
+
+    tree.descendants_named("ProjectReference")
+        .iter()
+        .filter_map(|element| element.attribute("include"))
+        .map(|include| project_dir.join(include.replace('\\', "/")).to_string_lossy().into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+impl ProjectGraph {
+    /// Build a graph directly from edges/direct-references, skipping the
+    /// filesystem and XML parsing `build` does, so the BFS/cycle-detection
+    /// algorithms can be tested in isolation.
+    fn from_parts(edges: HashMap<String, Vec<String>>, direct_references: HashSet<String>) -> ProjectGraph {
+        ProjectGraph { edges, direct_references }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(pairs: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        pairs.iter()
+            .map(|(project, refs)| (project.to_string(), refs.iter().map(|r| r.to_string()).collect()))
+            .collect()
+    }
+
+    fn set(items: &[&str]) -> HashSet<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn transitive_dependents_follows_chain_to_direct_reference() {
+        // A -> B -> C, where C directly references the queried DLL.
+        let graph = ProjectGraph::from_parts(
+            edges(&[("A", &["B"]), ("B", &["C"]), ("C", &[])]),
+            set(&["C"]),
+        );
+
+        let transitive = graph.transitive_dependents();
+        assert_eq!(transitive.get("A"), Some(&vec![vec!["A".to_string(), "B".to_string(), "C".to_string()]]));
+        assert_eq!(transitive.get("B"), Some(&vec![vec!["B".to_string(), "C".to_string()]]));
+        assert!(!transitive.contains_key("C"));
+    }
+
+    #[test]
+    fn transitive_dependents_is_empty_with_no_direct_references() {
+        let graph = ProjectGraph::from_parts(edges(&[("A", &["B"]), ("B", &[])]), set(&[]));
+        assert!(graph.transitive_dependents().is_empty());
+    }
+
+    #[test]
+    fn detect_cycles_finds_a_simple_cycle() {
+        // A -> B -> A. Which node the DFS starts from depends on HashMap
+        // iteration order, so accept either rotation of the cycle.
+        let graph = ProjectGraph::from_parts(edges(&[("A", &["B"]), ("B", &["A"])]), set(&[]));
+        let cycles = graph.detect_cycles();
+        assert_eq!(cycles.len(), 1);
+        let a_first = vec!["A".to_string(), "B".to_string(), "A".to_string()];
+        let b_first = vec!["B".to_string(), "A".to_string(), "B".to_string()];
+        assert!(cycles[0] == a_first || cycles[0] == b_first, "unexpected cycle: {:?}", cycles[0]);
+    }
+
+    #[test]
+    fn detect_cycles_is_empty_for_a_dag() {
+        let graph = ProjectGraph::from_parts(edges(&[("A", &["B"]), ("B", &["C"]), ("C", &[])]), set(&[]));
+        assert!(graph.detect_cycles().is_empty());
+    }
+}
@@ -1,10 +1,20 @@
-use std::{env, fs, str};
+mod binary;
+mod error;
+mod graph;
+mod query;
+mod report;
+mod solution;
+mod workspace;
+mod xml_tree;
+
+use std::{env, fs};
+use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::path::Path;
-use quick_xml::{Reader, events::Event};
+use serde::Serialize;
 
-#[derive(Debug, PartialEq)]
-enum ReferenceType {
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) enum ReferenceType {
     None,
     Reference,
     PackageReference,
@@ -23,9 +33,16 @@ impl Display for ReferenceType {
     }
 }
 
-struct ReferenceInfo {
+#[derive(Serialize)]
+pub(crate) struct ReferenceInfo {
     project_path: String,
     reference_type: ReferenceType,
+    /// The version declared by a `PackageReference` match (`"unknown"` when
+    /// the project declares none), or `None` for any other reference type.
+    package_version: Option<String>,
+    /// The solution this project was scanned as part of, or `None` for a
+    /// standalone project file discovered outside any solution.
+    solution_path: Option<String>,
 }
 
 const DEFAULT_SLN_PATH: &str = r#"d:\Development\Projects\StreamInfoHub\StreamInfoHub.sln"#;
@@ -33,155 +50,213 @@ const DEFAULT_DLL_NAME: &str = r#"Grpc.Tools"#;
 
 
 fn main() {
-    let mut args: Vec<String> = env::args().collect();
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() > 1 && args[1] == "--binary" {
+        run_binary_mode(&args);
+        return;
+    }
+
+    let mut args = args;
+    let format = report::parse_format_flag(&mut args);
 
-    // Add default parameters for testing purposes
-    args.push(DEFAULT_SLN_PATH.to_string());
-    args.push(DEFAULT_DLL_NAME.to_string());
+    if args.len() == 1 {
+        // No solution/DLL given at all: fall back to the defaults used for local testing.
+        args.push(DEFAULT_SLN_PATH.to_string());
+        args.push(DEFAULT_DLL_NAME.to_string());
+    }
 
     if args.len() != 3 {
-        println!("Usage: dlldepends <solution file path> <dll name>")
+        eprintln!("Usage: dlldepends <solution file path | directory> <dll name>[@<version-req>] [--format text|json]");
+        return;
     }
 
-    let solution_path = &args[1];
-    let dll_name = &args[2];
+    let target_path = &args[1];
+    let query = match query::DllQuery::parse(&args[2]) {
+        Ok(query) => query,
+        Err(e) => {
+            eprintln!("Invalid query \"{}\": {e}", &args[2]);
+            return;
+        }
+    };
+
+    let groups = if Path::new(target_path).is_dir() {
+        workspace::discover(Path::new(target_path))
+    } else {
+        match solution::parse(target_path) {
+            Ok(solution) => vec![workspace::SolutionGroup {
+                solution_path: Some(target_path.clone()),
+                project_paths: solution.project_paths(),
+                declared_dependencies: solution.dependency_paths(),
+            }],
+            Err(e) => {
+                eprintln!("Unable to read solution \"{target_path}\": {e}");
+                Vec::new()
+            }
+        }
+    };
+
+    let found_projects = groups.iter()
+        .flat_map(|group| scan_group(group, &query))
+        .collect::<Vec<_>>();
+
+    report::print_scan_report(&query.name, &found_projects, &format);
+
+    if format == report::OutputFormat::Text {
+        if groups.len() > 1 {
+            report::print_grouped_summary(&found_projects);
+        }
+        for group in &groups {
+            if group.solution_path.is_some() {
+                report_transitive_dependents(&group.project_paths, &query.name, &group.declared_dependencies);
+            }
+        }
+    }
+}
 
-    let found_projects = get_project_paths(&solution_path)
-        .into_iter()
+/// Scan every project in one solution group, returning a [`ReferenceInfo`]
+/// for each project with a matching reference.
+fn scan_group(group: &workspace::SolutionGroup, query: &query::DllQuery) -> Vec<ReferenceInfo> {
+    group.project_paths.iter()
         .filter_map(|project_path| {
-            let ref_type = check_dependency(&project_path, dll_name.clone());
-            match ref_type {
+            let result = match check_dependency(project_path, query) {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("Skipping \"{project_path}\": {e}");
+                    return None;
+                }
+            };
+            match result.reference_type {
                 ReferenceType::None => None,
                 _ => Some(ReferenceInfo {
-                    project_path,
-                    reference_type: ref_type
+                    project_path: project_path.clone(),
+                    reference_type: result.reference_type,
+                    package_version: result.package_version,
+                    solution_path: group.solution_path.clone(),
                 }),
             }
         })
-        .collect::<Vec<_>>();
+        .collect()
+}
 
-    if found_projects.is_empty() {
-        println!("No dependencies to \"{dll_name}\" were found in the project folder.");
-    } else {
-        println!("{} references to \"{dll_name}\" were found in the project folder.", found_projects.len());
+/// Build the project-reference graph for the whole solution and report every
+/// project that depends on `dll_name` transitively, i.e. through a chain of
+/// `ProjectReference` edges rather than a direct `Reference`/`PackageReference`.
+fn report_transitive_dependents(project_paths: &[String], dll_name: &str, declared_dependencies: &HashMap<String, Vec<String>>) {
+    let graph = graph::ProjectGraph::build(project_paths, dll_name, declared_dependencies);
+
+    for cycle in graph.detect_cycles() {
+        eprintln!("Warning: cycle detected in ProjectReference graph: {}", cycle.join(" -> "));
     }
-}
 
-fn get_project_paths(solution_path: &str) -> Vec<String> {
-    let solution_dir = Path::new(solution_path).parent().unwrap().to_path_buf();
-    let lines = fs::read_to_string(solution_path)
-        .expect("Unable to read solution file")
-        .lines()
-        .map(String::from)
-        .collect::<Vec<String>>();
-
-    lines.iter()
-        .filter(|line| line.starts_with("Project("))
-        .map(|line| {
-            let parts: Vec<&str> = line.split(',').collect();
-            let relative_path = parts[1].trim().trim_matches('"');
-            solution_dir.join(relative_path).to_string_lossy().into_owned()
-        })
-        .collect()
+    let transitive = graph.transitive_dependents();
+    if transitive.is_empty() {
+        return;
+    }
+
+    println!("{} project(s) depend on \"{dll_name}\" transitively:", transitive.len());
+    for (project, chains) in &transitive {
+        for chain in chains {
+            println!("  {} (via {})", project, chain.join(" -> "));
+        }
+    }
 }
 
-fn check_dependency(project_path: &str, mut dll_name: String) -> ReferenceType {
-    println!("Reading project: {}", project_path);
+/// `--binary <dll/exe path> <project file path>`: read the real dependencies
+/// out of the compiled binary and reconcile them against what the project
+/// file declares, flagging both "declared but unused" and "used but
+/// undeclared" references.
+fn run_binary_mode(args: &[String]) {
+    if args.len() != 4 {
+        println!("Usage: dlldepends --binary <dll/exe path> <project file path>");
+        return;
+    }
+
+    let binary_path = Path::new(&args[2]);
+    let project_path = &args[3];
+
+    let dependencies = match binary::inspect(binary_path) {
+        Ok(dependencies) => dependencies,
+        Err(e) => {
+            eprintln!("Unable to inspect \"{}\": {}", binary_path.display(), e);
+            return;
+        }
+    };
+
+    println!(
+        "Discovered {} assembly reference(s) and {} native import(s) in \"{}\".",
+        dependencies.assemblies.len(), dependencies.native_imports.len(), binary_path.display()
+    );
+    for assembly in &dependencies.assemblies {
+        let (major, minor, build, revision) = assembly.version;
+        println!("  {} ({major}.{minor}.{build}.{revision})", assembly.name);
+    }
+    for import in &dependencies.native_imports {
+        println!("  [native] {}", import);
+    }
+
+    let declared = binary::declared_references(project_path);
+    let discovered: Vec<String> = dependencies.assemblies.iter()
+        .map(|assembly| assembly.name.clone())
+        .chain(dependencies.native_imports.iter().cloned())
+        .collect();
+
+    let reconciliation = binary::reconcile(&declared, &discovered);
 
-    let xml = fs::read_to_string(project_path).expect("Unable to read project file");
-    let mut reader = Reader::from_str(&xml);
-    reader.trim_text(true);
+    if !reconciliation.declared_but_unused.is_empty() {
+        println!("Declared but unused:");
+        for name in &reconciliation.declared_but_unused {
+            println!("  {}", name);
+        }
+    }
+    if !reconciliation.used_but_undeclared.is_empty() {
+        println!("Used but undeclared:");
+        for name in &reconciliation.used_but_undeclared {
+            println!("  {}", name);
+        }
+    }
+}
 
+/// Strip a trailing `.dll` extension, if present, so callers can match
+/// project `Include` attributes (which name the assembly, not the file).
+pub(crate) fn normalize_dll_name(dll_name: &str) -> String {
     if dll_name.to_lowercase().ends_with(".dll") {
-        dll_name = Path::new(&dll_name)
+        Path::new(dll_name)
             .file_stem()
             .unwrap()
             .to_str()
             .unwrap()
-            .to_owned();
-    }
-
-    // get the namespace from xml
-    let mut buf = Vec::new();
-    let mut ns = String::new();
-    let mut sdk = String::new();
-
-    'outer: loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) => {
-                for attribute in e.attributes().filter_map(Result::ok) {
-                    let key_name = str::from_utf8(attribute.key.as_ref()).unwrap().to_lowercase();
-                    match key_name.as_ref() {
-                        "xmlns" => {
-                            ns = str::from_utf8(attribute.value.as_ref()).unwrap().to_string();
-                            buf.clear();
-                            break 'outer;
-                        },
-                        "sdk" => {
-                            sdk = str::from_utf8(attribute.value.as_ref()).unwrap().to_string();
-                            buf.clear();
-                            break 'outer;
-                        },
-                        _ => ()
-                    };
-                }
-            },
-            Ok(Event::Eof) => break 'outer,
-            _ => (),
-        }
+            .to_owned()
+    } else {
+        dll_name.to_owned()
     }
+}
 
-    let reference_types = vec![
-        ReferenceType::Reference,
-        ReferenceType::PackageReference,
-        ReferenceType::ProjectReference
-    ];
-    for rt in reference_types {
-        if has_reference(&xml, &rt, &dll_name) {
-            return rt;
-        }
+/// The outcome of scanning one project file: the kind of reference found
+/// (if any) and, for a `PackageReference` match, the concrete version.
+struct CheckResult {
+    reference_type: ReferenceType,
+    package_version: Option<String>,
+}
+
+fn check_dependency(project_path: &str, query: &query::DllQuery) -> Result<CheckResult, error::Error> {
+    eprintln!("Reading project: {}", project_path);
+
+    let xml = fs::read_to_string(project_path)?;
+    let tree = xml_tree::parse(&xml)?;
+    let query = query.with_name(normalize_dll_name(&query.name));
+
+    if tree.has_descendant_with_attribute("Reference", "include", &query.name) {
+        return Ok(CheckResult { reference_type: ReferenceType::Reference, package_version: None });
     }
 
-    ReferenceType::None
-}
+    if let Some(version) = query::match_package_reference(&tree, &query) {
+        return Ok(CheckResult { reference_type: ReferenceType::PackageReference, package_version: Some(version) });
+    }
 
-fn has_reference(xml: &str, reference_type: &ReferenceType, dll_name: &str) -> bool {
-    let expected_element_name = reference_type.to_string();
-    let mut reader = Reader::from_str(xml);
-    reader.trim_text(true);
-
-    println!("## scanning: {dll_name} ({reference_type})");
-    let mut buf = Vec::new();
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref element)) | Ok(Event::Empty(ref element)) => {
-                let element_name = element.name();
-                let element_name = str::from_utf8(element_name.as_ref()).unwrap();
-                if element_name == expected_element_name {
-                    for attribute in element.attributes().filter_map(Result::ok) {
-                        if let Ok(key) = str::from_utf8(attribute.key.as_ref()) {
-                            if key.to_lowercase() == "include" {
-                                let include_value = str::from_utf8(attribute.value.as_ref()).unwrap();
-                                println!("-----> {} vs {}", include_value, dll_name);
-                                if include_value == dll_name {
-                                    buf.clear();
-                                    return true;
-                                }
-                                if *reference_type == ReferenceType::ProjectReference {
-                                    println!("--> ProjectRef: {}", include_value)
-                                }
-                            }
-                        }
-                    }
-                }
-            },
-            Ok(Event::Eof) => break,
-            Err(e) => {
-                eprintln!("Error reading XML: {}", e);
-                break;
-            }
-            _ => ()
-        }
+    if tree.has_descendant_with_attribute("ProjectReference", "include", &query.name) {
+        return Ok(CheckResult { reference_type: ReferenceType::ProjectReference, package_version: None });
     }
-    false
+
+    Ok(CheckResult { reference_type: ReferenceType::None, package_version: None })
 }
@@ -0,0 +1,116 @@
+use semver::{Version, VersionReq};
+
+use crate::xml_tree::Element;
+
+/// A parsed `Name` or `Name@<version-req>` query, e.g. `Grpc.Tools@>=2.0,<3.0`,
+/// the same shape Cargo uses for dependency requirements.
+#[derive(Clone)]
+pub(crate) struct DllQuery {
+    pub name: String,
+    pub version_req: Option<VersionReq>,
+}
+
+impl DllQuery {
+    /// Parse `Name` or `Name@<version-req>`. A malformed version requirement
+    /// is reported as an error rather than silently degrading to "match any
+    /// version", since that would turn a typo into a query that matches
+    /// every `PackageReference` regardless of version.
+    pub fn parse(input: &str) -> Result<DllQuery, String> {
+        match input.split_once('@') {
+            Some((name, req)) => {
+                let version_req = VersionReq::parse(req)
+                    .map_err(|e| format!("invalid version requirement \"{req}\": {e}"))?;
+                Ok(DllQuery { name: name.to_string(), version_req: Some(version_req) })
+            },
+            None => Ok(DllQuery { name: input.to_string(), version_req: None }),
+        }
+    }
+
+    pub fn with_name(&self, name: String) -> DllQuery {
+        DllQuery { name, version_req: self.version_req.clone() }
+    }
+}
+
+/// Find a `PackageReference` in `tree` whose `Include` matches `query.name`,
+/// reading the version from either the `Version` attribute or the nested
+/// `<Version>` child element SDK-style projects use. Returns the concrete
+/// version found when the `Include` matches and (if present) the version
+/// requirement is satisfied; with no requirement, a match that declares no
+/// version returns `"unknown"`.
+pub(crate) fn match_package_reference(tree: &Element, query: &DllQuery) -> Option<String> {
+    tree.descendants_named("PackageReference")
+        .into_iter()
+        .filter(|element| element.attribute("include") == Some(query.name.as_str()))
+        .find_map(|element| {
+            let version = element.attribute("version")
+                .map(str::to_string)
+                .or_else(|| element.children_named("Version").next().map(|v| v.text.trim().to_string()));
+
+            satisfies(&version, &query.version_req).then(|| version.unwrap_or_else(|| "unknown".to_string()))
+        })
+}
+
+fn satisfies(version: &Option<String>, req: &Option<VersionReq>) -> bool {
+    match (version, req) {
+        (_, None) => true,
+        (Some(version), Some(req)) => Version::parse(&normalize_version(version))
+            .map(|parsed| req.matches(&parsed))
+            .unwrap_or(false),
+        (None, Some(_)) => false,
+    }
+}
+
+/// semver requires exactly `major.minor.patch`; pad or truncate a .NET-style
+/// version (which may have two or four components) to fit.
+fn normalize_version(version: &str) -> String {
+    let mut parts: Vec<&str> = version.split('.').take(3).collect();
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    parts.join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_without_version_req_matches_any_version() {
+        let query = DllQuery::parse("Grpc.Tools").unwrap();
+        assert_eq!(query.name, "Grpc.Tools");
+        assert!(query.version_req.is_none());
+    }
+
+    #[test]
+    fn parse_with_version_req_splits_name_and_requirement() {
+        let query = DllQuery::parse("Grpc.Tools@>=2.0,<3.0").unwrap();
+        assert_eq!(query.name, "Grpc.Tools");
+        assert!(query.version_req.unwrap().matches(&Version::parse("2.50.0").unwrap()));
+    }
+
+    #[test]
+    fn parse_rejects_a_malformed_version_requirement() {
+        assert!(DllQuery::parse("Grpc.Tools@not-a-range").is_err());
+    }
+
+    #[test]
+    fn normalize_version_pads_two_component_dotnet_versions() {
+        assert_eq!(normalize_version("2.50"), "2.50.0");
+    }
+
+    #[test]
+    fn normalize_version_truncates_four_component_dotnet_versions() {
+        assert_eq!(normalize_version("2.50.1.0"), "2.50.1");
+    }
+
+    #[test]
+    fn satisfies_is_true_with_no_requirement_even_without_a_version() {
+        assert!(satisfies(&None, &None));
+    }
+
+    #[test]
+    fn satisfies_is_false_when_a_requirement_exists_but_no_version_was_found() {
+        let req = VersionReq::parse(">=1.0").unwrap();
+        assert!(!satisfies(&None, &Some(req)));
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::ReferenceInfo;
+
+/// Output mode for the scan report: human-readable text (the historical
+/// behavior) or a single JSON document for consumption by other tooling.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// The scan result as a serializable document: the queried DLL, every
+/// matching project with its reference type, and the total match count.
+#[derive(Serialize)]
+struct ScanReport<'a> {
+    dll_name: &'a str,
+    match_count: usize,
+    matches: &'a [ReferenceInfo],
+}
+
+/// Pull `--format text|json` out of `args`, wherever it appears, defaulting
+/// to `Text` when absent or unrecognized.
+pub(crate) fn parse_format_flag(args: &mut Vec<String>) -> OutputFormat {
+    let Some(pos) = args.iter().position(|arg| arg == "--format") else {
+        return OutputFormat::Text;
+    };
+
+    let format = match args.get(pos + 1).map(String::as_str) {
+        Some("json") => OutputFormat::Json,
+        _ => OutputFormat::Text,
+    };
+
+    let end = (pos + 2).min(args.len());
+    args.drain(pos..end);
+    format
+}
+
+/// Print a per-solution breakdown of how many matches each group of
+/// projects (one per discovered solution, plus standalone projects under
+/// "(no solution)") contributed, for a recursive workspace scan.
+pub(crate) fn print_grouped_summary(matches: &[ReferenceInfo]) {
+    let mut by_solution: BTreeMap<String, usize> = BTreeMap::new();
+    for reference in matches {
+        let key = reference.solution_path.clone().unwrap_or_else(|| "(no solution)".to_string());
+        *by_solution.entry(key).or_insert(0) += 1;
+    }
+
+    println!("By solution:");
+    for (solution, count) in by_solution {
+        println!("  {solution}: {count} reference(s)");
+    }
+}
+
+/// Print the scan result in the requested format.
+pub(crate) fn print_scan_report(dll_name: &str, matches: &[ReferenceInfo], format: &OutputFormat) {
+    match format {
+        OutputFormat::Text => {
+            if matches.is_empty() {
+                println!("No dependencies to \"{dll_name}\" were found in the project folder.");
+            } else {
+                println!("{} references to \"{dll_name}\" were found in the project folder.", matches.len());
+            }
+        }
+        OutputFormat::Json => {
+            let report = ScanReport { dll_name, match_count: matches.len(), matches };
+            match serde_json::to_string_pretty(&report) {
+                Ok(json) => println!("{json}"),
+                Err(e) => eprintln!("Unable to serialize scan report: {e}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_format_flag_defaults_to_text_when_absent() {
+        let mut args = args(&["dlldepends", "App.sln", "Grpc.Tools"]);
+        assert_eq!(parse_format_flag(&mut args), OutputFormat::Text);
+        assert_eq!(args, vec!["dlldepends", "App.sln", "Grpc.Tools"]);
+    }
+
+    #[test]
+    fn parse_format_flag_recognizes_json_and_removes_both_args() {
+        let mut args = args(&["dlldepends", "App.sln", "Grpc.Tools", "--format", "json"]);
+        assert_eq!(parse_format_flag(&mut args), OutputFormat::Json);
+        assert_eq!(args, vec!["dlldepends", "App.sln", "Grpc.Tools"]);
+    }
+
+    #[test]
+    fn parse_format_flag_defaults_to_text_for_an_unrecognized_value() {
+        let mut args = args(&["dlldepends", "App.sln", "Grpc.Tools", "--format", "xml"]);
+        assert_eq!(parse_format_flag(&mut args), OutputFormat::Text);
+        assert_eq!(args, vec!["dlldepends", "App.sln", "Grpc.Tools"]);
+    }
+
+    #[test]
+    fn parse_format_flag_works_regardless_of_position() {
+        let mut args = args(&["dlldepends", "--format", "json", "App.sln", "Grpc.Tools"]);
+        assert_eq!(parse_format_flag(&mut args), OutputFormat::Json);
+        assert_eq!(args, vec!["dlldepends", "App.sln", "Grpc.Tools"]);
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The well-known project-type GUID Visual Studio uses for solution
+/// folders. Folder entries have no file on disk, so they must be
+/// recognized and skipped rather than resolved as a project path.
+const SOLUTION_FOLDER_GUID: &str = "{2150E333-8FDC-42A3-9474-1A3956D46DE8}";
+const CSHARP_LEGACY_GUID: &str = "{FAE04EC0-301F-11D3-BF4B-0C04F79EFBC7}";
+const CSHARP_SDK_GUID: &str = "{9A19103F-16F7-4668-BE54-9A1E7A4F7556}";
+const VISUAL_BASIC_GUID: &str = "{F184B08F-C81C-45F6-A57F-5ABD9991F28F}";
+const FSHARP_GUID: &str = "{F2A71F9B-5D33-465A-A702-920D77279786}";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ProjectKind {
+    CSharp,
+    VisualBasic,
+    FSharp,
+    Folder,
+    /// Any other project-type GUID (native C++, database, setup, ...), kept
+    /// (upper-cased) rather than guessed at.
+    Other(String),
+}
+
+/// One `Project(...) = ...` entry from a `.sln` file.
+#[derive(Debug, Clone)]
+pub(crate) struct ProjectEntry {
+    pub name: String,
+    pub guid: String,
+    pub path: String,
+    pub kind: ProjectKind,
+}
+
+/// A parsed solution: every project entry plus the explicit inter-project
+/// dependency GUIDs declared in `ProjectSection(ProjectDependencies)` blocks,
+/// keyed by the depending project's GUID.
+#[derive(Debug, Default)]
+pub(crate) struct SolutionFile {
+    pub projects: Vec<ProjectEntry>,
+    pub dependencies: HashMap<String, Vec<String>>,
+}
+
+impl SolutionFile {
+    /// Project file paths for every entry that isn't a solution folder, i.e.
+    /// everything the DLL-reference scan should actually open and parse.
+    pub fn project_paths(&self) -> Vec<String> {
+        self.projects.iter()
+            .filter(|project| project.kind != ProjectKind::Folder)
+            .map(|project| project.path.clone())
+            .collect()
+    }
+
+    /// The `ProjectSection(ProjectDependencies)` GUIDs resolved to project
+    /// paths, so [`crate::graph::ProjectGraph`] can use them as edges
+    /// alongside `ProjectReference` includes. A dependency GUID that isn't
+    /// one of this solution's own projects is reported and dropped, rather
+    /// than silently producing a dangling edge.
+    pub fn dependency_paths(&self) -> HashMap<String, Vec<String>> {
+        let path_by_guid: HashMap<&str, &str> = self.projects.iter()
+            .map(|project| (project.guid.as_str(), project.path.as_str()))
+            .collect();
+
+        self.dependencies.iter()
+            .filter_map(|(guid, dependency_guids)| {
+                let path = *path_by_guid.get(guid.as_str())?;
+                let dependency_paths = dependency_guids.iter()
+                    .filter_map(|dependency_guid| match path_by_guid.get(dependency_guid.as_str()) {
+                        Some(path) => Some(path.to_string()),
+                        None => {
+                            let name = self.projects.iter()
+                                .find(|project| &project.guid == guid)
+                                .map(|project| project.name.as_str())
+                                .unwrap_or(guid);
+                            eprintln!("\"{name}\" declares a dependency on unknown project GUID {dependency_guid}");
+                            None
+                        }
+                    })
+                    .collect();
+                Some((path.to_string(), dependency_paths))
+            })
+            .collect()
+    }
+}
+
+/// Parse a `.sln` file's `Project(...)`/`EndProject` and nested
+/// `ProjectSection(ProjectDependencies)` blocks into structured entries,
+/// instead of naively splitting each `Project(` line on commas.
+pub(crate) fn parse(solution_path: &str) -> Result<SolutionFile, crate::error::Error> {
+    let solution_dir = Path::new(solution_path).parent().unwrap().to_path_buf();
+    let contents = fs::read_to_string(solution_path)?;
+
+    let mut solution = SolutionFile::default();
+    let mut current_project_guid: Option<String> = None;
+    let mut in_dependencies_section = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+
+        if let Some(rest) = line.strip_prefix("Project(") {
+            let fields = quoted_segments(rest);
+            if let [type_guid, name, path, guid] = &fields[..] {
+                let project = ProjectEntry {
+                    name: name.clone(),
+                    guid: guid.clone(),
+                    path: solution_dir.join(path.replace('\\', "/")).to_string_lossy().into_owned(),
+                    kind: classify(type_guid),
+                };
+                current_project_guid = Some(project.guid.clone());
+                solution.projects.push(project);
+            }
+            continue;
+        }
+
+        if line == "EndProject" {
+            current_project_guid = None;
+            continue;
+        }
+
+        if line.starts_with("ProjectSection(ProjectDependencies)") {
+            in_dependencies_section = true;
+            continue;
+        }
+
+        if line == "EndProjectSection" {
+            in_dependencies_section = false;
+            continue;
+        }
+
+        if in_dependencies_section {
+            if let Some((dependency_guid, _)) = line.split_once('=') {
+                if let Some(project_guid) = &current_project_guid {
+                    solution.dependencies.entry(project_guid.clone())
+                        .or_default()
+                        .push(dependency_guid.trim().to_string());
+                }
+            }
+        }
+    }
+
+    Ok(solution)
+}
+
+fn classify(type_guid: &str) -> ProjectKind {
+    match type_guid.to_uppercase().as_str() {
+        g if g == SOLUTION_FOLDER_GUID => ProjectKind::Folder,
+        g if g == CSHARP_LEGACY_GUID || g == CSHARP_SDK_GUID => ProjectKind::CSharp,
+        g if g == VISUAL_BASIC_GUID => ProjectKind::VisualBasic,
+        g if g == FSHARP_GUID => ProjectKind::FSharp,
+        other => ProjectKind::Other(other.to_string()),
+    }
+}
+
+/// Extract every double-quoted substring from `line`, in order. This sidesteps
+/// comma-splitting entirely, so solution folders, quoted paths containing
+/// commas, and the GUID fields all come out correctly.
+fn quoted_segments(line: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '"' {
+            continue;
+        }
+        let mut segment = String::new();
+        for next in chars.by_ref() {
+            if next == '"' {
+                break;
+            }
+            segment.push(next);
+        }
+        segments.push(segment);
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_segments_ignores_commas_inside_quotes() {
+        let fields = quoted_segments(r#""{GUID-1}") = "My, Project", "src\My.Project\My.Project.csproj", "{GUID-2}""#);
+        assert_eq!(fields, vec!["{GUID-1}", "My, Project", r"src\My.Project\My.Project.csproj", "{GUID-2}"]);
+    }
+
+    #[test]
+    fn classify_recognizes_the_well_known_project_type_guids() {
+        assert_eq!(classify(SOLUTION_FOLDER_GUID), ProjectKind::Folder);
+        assert_eq!(classify(CSHARP_SDK_GUID), ProjectKind::CSharp);
+        assert_eq!(classify("{00000000-0000-0000-0000-000000000000}"), ProjectKind::Other("{00000000-0000-0000-0000-000000000000}".to_string()));
+    }
+
+    #[test]
+    fn parse_reads_projects_and_dependencies_from_a_sln_file() {
+        let dir = std::env::temp_dir().join("dlldepends-solution-test");
+        fs::create_dir_all(&dir).unwrap();
+        let sln_path = dir.join("Test.sln");
+        fs::write(&sln_path, concat!(
+            "Microsoft Visual Studio Solution File, Format Version 12.00\n",
+            "Project(\"{9A19103F-16F7-4668-BE54-9A1E7A4F7556}\") = \"A\", \"A\\A.csproj\", \"{AAAAAAAA-AAAA-AAAA-AAAA-AAAAAAAAAAAA}\"\n",
+            "\tProjectSection(ProjectDependencies) = postProject\n",
+            "\t\t{BBBBBBBB-BBBB-BBBB-BBBB-BBBBBBBBBBBB} = {BBBBBBBB-BBBB-BBBB-BBBB-BBBBBBBBBBBB}\n",
+            "\tEndProjectSection\n",
+            "EndProject\n",
+            "Project(\"{9A19103F-16F7-4668-BE54-9A1E7A4F7556}\") = \"B\", \"B\\B.csproj\", \"{BBBBBBBB-BBBB-BBBB-BBBB-BBBBBBBBBBBB}\"\n",
+            "EndProject\n",
+        )).unwrap();
+
+        let solution = parse(sln_path.to_str().unwrap()).unwrap();
+        assert_eq!(solution.projects.len(), 2);
+        assert_eq!(solution.project_paths().len(), 2);
+
+        let dependencies = solution.dependency_paths();
+        let a_path = dir.join("A").join("A.csproj").to_string_lossy().into_owned();
+        let b_path = dir.join("B").join("B.csproj").to_string_lossy().into_owned();
+        assert_eq!(dependencies.get(&a_path), Some(&vec![b_path]));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
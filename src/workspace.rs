@@ -0,0 +1,137 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::solution;
+
+/// The projects that belong together for reporting purposes: every project
+/// covered by one discovered `.sln`, or a synthetic "no solution" bucket for
+/// standalone project files that aren't a member of any solution found.
+pub(crate) struct SolutionGroup {
+    pub solution_path: Option<String>,
+    pub project_paths: Vec<String>,
+    /// Inter-project dependency edges declared in the solution's own
+    /// `ProjectSection(ProjectDependencies)` blocks, empty for a standalone
+    /// group with no solution.
+    pub declared_dependencies: HashMap<String, Vec<String>>,
+}
+
+/// Recursively discover every `.sln`, `.csproj`, `.vbproj` and `.fsproj`
+/// under `root`, so a whole repository of projects can be audited in one
+/// invocation instead of pointing at a single solution.
+pub(crate) fn discover(root: &Path) -> Vec<SolutionGroup> {
+    let mut solution_paths = Vec::new();
+    let mut project_paths = Vec::new();
+    walk(root, &mut solution_paths, &mut project_paths);
+
+    let mut seen_projects: HashSet<String> = HashSet::new();
+    let mut groups: Vec<SolutionGroup> = Vec::new();
+
+    for solution_path in &solution_paths {
+        match solution::parse(solution_path) {
+            Ok(solution) => {
+                let projects = solution.project_paths();
+                seen_projects.extend(projects.iter().map(|project| normalize(project)));
+                groups.push(SolutionGroup {
+                    solution_path: Some(solution_path.clone()),
+                    declared_dependencies: solution.dependency_paths(),
+                    project_paths: projects,
+                });
+            },
+            Err(e) => eprintln!("Skipping \"{solution_path}\": {e}"),
+        }
+    }
+
+    let standalone: Vec<String> = project_paths.into_iter()
+        .filter(|project| !seen_projects.contains(&normalize(project)))
+        .collect();
+
+    if !standalone.is_empty() {
+        groups.push(SolutionGroup { solution_path: None, project_paths: standalone, declared_dependencies: HashMap::new() });
+    }
+
+    groups
+}
+
+/// Normalize a path for equality comparison, so a project reached via a
+/// `.sln`'s `..`-relative path (e.g. `"..\Common\Common.csproj"`) compares
+/// equal to the same file found by the directory walk. Falls back to a
+/// lexical `..`-collapse when the file doesn't exist (e.g. a stale solution
+/// reference), since [`fs::canonicalize`] requires the path to resolve.
+fn normalize(path: &str) -> String {
+    match fs::canonicalize(path) {
+        Ok(canonical) => canonical.to_string_lossy().into_owned(),
+        Err(_) => lexically_normalize(path),
+    }
+}
+
+fn lexically_normalize(path: &str) -> String {
+    use std::path::Component;
+
+    let mut normalized = std::path::PathBuf::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::ParentDir => { normalized.pop(); },
+            Component::CurDir => (),
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized.to_string_lossy().into_owned()
+}
+
+fn walk(dir: &Path, solutions: &mut Vec<String>, projects: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_dir() {
+            walk(&path, solutions, projects);
+            continue;
+        }
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("sln") => solutions.push(path.to_string_lossy().into_owned()),
+            Some("csproj") | Some("vbproj") | Some("fsproj") => projects.push(path.to_string_lossy().into_owned()),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lexically_normalize_collapses_parent_dir_segments() {
+        assert_eq!(lexically_normalize("/root/App/../Common/Common.csproj"), "/root/Common/Common.csproj");
+    }
+
+    /// A project reached from a `.sln` via a `..`-relative path (common for
+    /// a shared project referenced by multiple sibling solutions) must not
+    /// also be reported as a standalone, solution-less project just because
+    /// the directory walk found it under a different-looking path string.
+    #[test]
+    fn discover_does_not_duplicate_a_project_referenced_via_a_parent_relative_path() {
+        let root = std::env::temp_dir().join("dlldepends-workspace-test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("App")).unwrap();
+        fs::create_dir_all(root.join("Common")).unwrap();
+
+        fs::write(root.join("App").join("App.csproj"), "<Project></Project>").unwrap();
+        fs::write(root.join("Common").join("Common.csproj"), "<Project></Project>").unwrap();
+        fs::write(root.join("App").join("App.sln"), concat!(
+            "Microsoft Visual Studio Solution File, Format Version 12.00\n",
+            "Project(\"{9A19103F-16F7-4668-BE54-9A1E7A4F7556}\") = \"App\", \"App.csproj\", \"{AAAAAAAA-AAAA-AAAA-AAAA-AAAAAAAAAAAA}\"\n",
+            "EndProject\n",
+            "Project(\"{9A19103F-16F7-4668-BE54-9A1E7A4F7556}\") = \"Common\", \"..\\Common\\Common.csproj\", \"{BBBBBBBB-BBBB-BBBB-BBBB-BBBBBBBBBBBB}\"\n",
+            "EndProject\n",
+        )).unwrap();
+
+        let groups = discover(&root);
+
+        assert_eq!(groups.len(), 1, "Common.csproj should not spawn a second, solution-less group");
+        assert_eq!(groups[0].project_paths.len(), 2);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
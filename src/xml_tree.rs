@@ -0,0 +1,159 @@
+use std::str;
+use quick_xml::{Reader, events::Event};
+
+use crate::error::Error;
+
+/// One element of a project file, built once from the raw XML events so the
+/// rest of the pipeline can query it directly instead of re-parsing the
+/// file for every question it wants answered.
+#[derive(Debug, Default)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    /// Case-insensitively look up an attribute, matching the repo's existing
+    /// convention of lower-casing attribute keys before comparing (project
+    /// files are inconsistent about `Include` vs `include`).
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.iter()
+            .find(|(name, _)| name.to_lowercase() == key.to_lowercase())
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn children_named<'a, 'b>(&'a self, name: &'b str) -> impl Iterator<Item = &'a Element> + 'b
+    where
+        'a: 'b,
+    {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// Every descendant (at any depth) with the given element name, in
+    /// document order.
+    pub fn descendants_named(&self, name: &str) -> Vec<&Element> {
+        let mut found = Vec::new();
+        self.collect_descendants_named(name, &mut found);
+        found
+    }
+
+    /// Whether any descendant named `element_name` has an attribute `key`
+    /// whose value is exactly `value`.
+    pub fn has_descendant_with_attribute(&self, element_name: &str, key: &str, value: &str) -> bool {
+        self.descendants_named(element_name).iter().any(|element| element.attribute(key) == Some(value))
+    }
+
+    fn collect_descendants_named<'a>(&'a self, name: &str, found: &mut Vec<&'a Element>) {
+        for child in &self.children {
+            if child.name == name {
+                found.push(child);
+            }
+            child.collect_descendants_named(name, found);
+        }
+    }
+}
+
+/// Parse a project file's XML into an [`Element`] tree in a single pass,
+/// under a synthetic root (the real root, usually `<Project>`, is its only
+/// child) so callers don't need to special-case "is this the document root".
+pub(crate) fn parse(xml: &str) -> Result<Element, Error> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut root = Element::default();
+    let mut stack: Vec<Element> = vec![];
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(ref e) => {
+                stack.push(element_from_start(e)?);
+            },
+            Event::Empty(ref e) => {
+                let element = element_from_start(e)?;
+                push_child(&mut stack, &mut root, element);
+            },
+            Event::Text(ref t) => {
+                if let Some(current) = stack.last_mut() {
+                    current.text.push_str(&t.unescape()?);
+                }
+            },
+            Event::End(_) => {
+                if let Some(element) = stack.pop() {
+                    push_child(&mut stack, &mut root, element);
+                }
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+        buf.clear();
+    }
+
+    Ok(root)
+}
+
+fn push_child(stack: &mut [Element], root: &mut Element, child: Element) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(child),
+        None => root.children.push(child),
+    }
+}
+
+fn element_from_start(start: &quick_xml::events::BytesStart) -> Result<Element, Error> {
+    let name = str::from_utf8(start.name().as_ref())?.to_string();
+    let mut attributes = Vec::new();
+
+    for attribute in start.attributes().filter_map(Result::ok) {
+        let key = str::from_utf8(attribute.key.as_ref())?.to_string();
+        let value = str::from_utf8(attribute.value.as_ref())?.to_string();
+        attributes.push((key, value));
+    }
+
+    Ok(Element { name, attributes, children: Vec::new(), text: String::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_builds_a_tree_under_a_synthetic_root() {
+        let tree = parse(r#"<Project><ItemGroup><Reference Include="Foo" /></ItemGroup></Project>"#).unwrap();
+
+        assert_eq!(tree.children.len(), 1);
+        let project = &tree.children[0];
+        assert_eq!(project.name, "Project");
+        assert_eq!(project.children[0].name, "ItemGroup");
+        assert_eq!(project.children[0].children[0].name, "Reference");
+    }
+
+    #[test]
+    fn parse_captures_child_element_text() {
+        let tree = parse(r#"<Project><PackageReference Include="Foo"><Version>1.2.3</Version></PackageReference></Project>"#).unwrap();
+        let reference = &tree.children[0].children[0];
+        assert_eq!(reference.children_named("Version").next().unwrap().text, "1.2.3");
+    }
+
+    #[test]
+    fn attribute_lookup_is_case_insensitive() {
+        let tree = parse(r#"<Project><Reference INCLUDE="Foo" /></Project>"#).unwrap();
+        let reference = &tree.children[0].children[0];
+        assert_eq!(reference.attribute("include"), Some("Foo"));
+    }
+
+    #[test]
+    fn descendants_named_finds_elements_at_any_depth() {
+        let tree = parse(r#"<Project><ItemGroup><ProjectReference Include="A" /><ProjectReference Include="B" /></ItemGroup></Project>"#).unwrap();
+        let refs = tree.descendants_named("ProjectReference");
+        assert_eq!(refs.len(), 2);
+    }
+
+    #[test]
+    fn has_descendant_with_attribute_matches_on_exact_value() {
+        let tree = parse(r#"<Project><ItemGroup><Reference Include="Grpc.Tools" /></ItemGroup></Project>"#).unwrap();
+        assert!(tree.has_descendant_with_attribute("Reference", "include", "Grpc.Tools"));
+        assert!(!tree.has_descendant_with_attribute("Reference", "include", "Other.Dll"));
+    }
+}